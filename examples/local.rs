@@ -1,4 +1,11 @@
 //! examples/local.rs
+//!
+//! Illustrative only: demonstrates the `#[app]` macro's intended surface
+//! (`#[shared]`/`#[local]` structs, `shared = [..]`/`local = [..]` task
+//! args). This crate ships the macro's runtime-support half only -- no
+//! `#[rtic::app]` proc macro exists in this dependency tree yet, so this
+//! file can't actually be compiled; see `Cargo.toml`'s `autoexamples =
+//! false`.
 
 #![deny(unsafe_code)]
 #![deny(warnings)]
@@ -6,74 +13,81 @@
 #![no_std]
 
 use cortex_m_semihosting::{debug, hprintln};
-use lm3s6965::Interrupt;
 use panic_semihosting as _;
 
+// Written once, called from `uart0` and `uart1` alike, regardless of
+// whether the caller already holds the lock or has to take it -- see
+// `rtic::Mutex`.
+fn advance(mut shared: impl rtic::Mutex<T = u32>) -> u32 {
+    shared.lock(|shared| {
+        *shared += 1;
+        *shared
+    })
+}
+
 #[rtic::app(device = lm3s6965)]
-const APP: () = {
-    struct Resources {
-        // An early resource
-        #[init(0)]
-        shared: u32,
+mod app {
+    use lm3s6965::Interrupt;
 
-        // A local (move), early resource
-        #[task_local]
-        #[init(1)]
-        l1: u32,
+    use super::{advance, debug, hprintln};
 
-        // An exclusive, early resource
-        #[lock_free]
-        #[init(1)]
+    // Fields here are lockable from any task that names them in `shared`,
+    // at the cost of going through `cx.shared.$field.lock(..)`.
+    #[shared]
+    struct Shared {
+        shared: u32,
+        // Accessed from both `uart0` and `uart1`; can't be `#[local]`.
         e1: u32,
+    }
 
-        // A local (move), late resource
-        #[task_local]
+    // Each field here is moved to exactly one task's `local` list below;
+    // the macro rejects the app at compile time if two tasks claim the
+    // same field.
+    #[local]
+    struct Local {
+        l1: u32,
         l2: u32,
-
-        // An exclusive, late resource
-        #[lock_free]
         e2: u32,
     }
 
     #[init]
-    fn init(_: init::Context) -> init::LateResources {
+    fn init(_: init::Context) -> (Shared, Local, init::Monotonics) {
         rtic::pend(Interrupt::UART0);
         rtic::pend(Interrupt::UART1);
-        init::LateResources { e2: 2, l2: 2 }
+
+        (
+            Shared { shared: 0, e1: 1 },
+            Local { l1: 1, l2: 2, e2: 2 },
+            init::Monotonics(),
+        )
     }
 
-    // `shared` cannot be accessed from this context
-    // l1 ok (task_local)
-    // e2 ok (lock_free)
-    #[idle(resources =[l1, e2])]
+    #[idle(local = [l1, e2])]
     fn idle(cx: idle::Context) -> ! {
-        hprintln!("IDLE:l1 = {}", cx.resources.l1).unwrap();
-        hprintln!("IDLE:e2 = {}", cx.resources.e2).unwrap();
+        hprintln!("IDLE:l1 = {}", cx.local.l1).unwrap();
+        hprintln!("IDLE:e2 = {}", cx.local.e2).unwrap();
         debug::exit(debug::EXIT_SUCCESS);
         loop {}
     }
 
-    // `shared` can be accessed from this context
-    // l2 ok (task_local)
-    // e1 ok (lock_free)
-    #[task(priority = 1, binds = UART0, resources = [shared, l2, e1])]
+    #[task(priority = 1, binds = UART0, shared = [shared, e1], local = [l2])]
     fn uart0(cx: uart0::Context) {
-        let shared: &mut u32 = cx.resources.shared;
-        *shared += 1;
-        *cx.resources.e1 += 10;
+        let shared = advance(cx.shared.shared);
+        advance(cx.shared.e1);
+
         hprintln!("UART0: shared = {}", shared).unwrap();
-        hprintln!("UART0:l2 = {}", cx.resources.l2).unwrap();
-        hprintln!("UART0:e1 = {}", cx.resources.e1).unwrap();
+        hprintln!("UART0:l2 = {}", cx.local.l2).unwrap();
     }
 
-    // `shared` can be accessed from this context
-    // e1 ok (lock_free)
-    #[task(priority = 1, binds = UART1, resources = [shared, e1])]
+    // `calls` is declared inline rather than as a `#[local]` struct field,
+    // since no other task needs it.
+    #[task(priority = 1, binds = UART1, shared = [shared, e1], local = [calls: u32 = 0])]
     fn uart1(cx: uart1::Context) {
-        let shared: &mut u32 = cx.resources.shared;
-        *shared += 1;
+        let shared = advance(cx.shared.shared);
+        advance(cx.shared.e1);
+        *cx.local.calls += 1;
 
         hprintln!("UART1: shared = {}", shared).unwrap();
-        hprintln!("UART1:e1 = {}", cx.resources.e1).unwrap();
+        hprintln!("UART1:calls = {}", cx.local.calls).unwrap();
     }
-};
+}
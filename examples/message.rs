@@ -0,0 +1,65 @@
+//! examples/message.rs
+//!
+//! A software task that is not bound to an interrupt: it's dispatched on
+//! demand via `spawn`, with a typed message payload. Dispatching happens
+//! on `QEI0`, an otherwise-unused NVIC line reserved via `dispatchers`.
+//!
+//! Illustrative only: no `#[rtic::app]` proc macro exists in this
+//! dependency tree, so `log::spawn` and the `#[shared]`/`#[local]`
+//! structs below aren't backed by anything that can expand this file.
+//! This crate ships the `ready_queue` module that such a macro's
+//! generated dispatcher would call into; see `Cargo.toml`'s
+//! `autoexamples = false`.
+
+#![deny(unsafe_code)]
+#![deny(warnings)]
+#![no_main]
+#![no_std]
+
+use panic_semihosting as _;
+
+#[rtic::app(device = lm3s6965, dispatchers = [QEI0])]
+mod app {
+    use cortex_m_semihosting::{debug, hprintln};
+    use lm3s6965::Interrupt;
+
+    #[shared]
+    struct Shared {}
+
+    #[local]
+    struct Local {}
+
+    #[init]
+    fn init(_: init::Context) -> (Shared, Local, init::Monotonics) {
+        rtic::pend(Interrupt::UART0);
+
+        (Shared {}, Local {}, init::Monotonics())
+    }
+
+    #[idle]
+    fn idle(_: idle::Context) -> ! {
+        loop {
+            cortex_m::asm::nop();
+        }
+    }
+
+    #[task(binds = UART0)]
+    fn uart0(_: uart0::Context) {
+        // `log::spawn` runs at `log`'s own priority, on the `QEI0`
+        // dispatcher, rather than inline in this ISR.
+        for i in 0..3 {
+            if log::spawn(i).is_err() {
+                hprintln!("log queue is full, dropping {}", i).unwrap();
+            }
+        }
+    }
+
+    #[task(capacity = 3)]
+    fn log(_: log::Context, byte: u8) {
+        hprintln!("log: {}", byte).unwrap();
+
+        if byte == 2 {
+            debug::exit(debug::EXIT_SUCCESS);
+        }
+    }
+}
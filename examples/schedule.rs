@@ -0,0 +1,51 @@
+//! examples/schedule.rs
+//!
+//! Demonstrates a monotonic clock source and a periodic software task
+//! that reschedules itself with `spawn_at`.
+//!
+//! Illustrative only: no `#[rtic::app]` proc macro exists in this
+//! dependency tree, so this file can't actually be compiled; see
+//! `Cargo.toml`'s `autoexamples = false`.
+
+#![deny(unsafe_code)]
+#![deny(warnings)]
+#![no_main]
+#![no_std]
+
+use panic_semihosting as _;
+
+#[rtic::app(device = lm3s6965, monotonic = rtic::cyccnt::CYCCNT)]
+mod app {
+    use cortex_m_semihosting::hprintln;
+    use rtic::cyccnt::U32Ext;
+
+    const PERIOD: u32 = 1_000_000;
+
+    #[shared]
+    struct Shared {}
+
+    #[local]
+    struct Local {}
+
+    #[init]
+    fn init(cx: init::Context) -> (Shared, Local, init::Monotonics) {
+        periodic::spawn_after(PERIOD.cycles()).unwrap();
+
+        (Shared {}, Local {}, init::Monotonics(cx.core.DWT, cx.core.DCB))
+    }
+
+    #[idle]
+    fn idle(_: idle::Context) -> ! {
+        loop {
+            cortex_m::asm::wfi();
+        }
+    }
+
+    #[task]
+    fn periodic(cx: periodic::Context) {
+        hprintln!("periodic: called at {:?}", cx.scheduled).unwrap();
+
+        // Re-schedule from within the task body for a periodic task.
+        periodic::spawn_at(cx.scheduled + PERIOD.cycles()).unwrap();
+    }
+}
@@ -1,3 +1,9 @@
+//! examples/t-idle-main.rs
+//!
+//! Illustrative only: no `#[rtic::app]` proc macro exists in this
+//! dependency tree, so this file can't actually be compiled; see
+//! `Cargo.toml`'s `autoexamples = false`.
+
 #![deny(unsafe_code)]
 #![deny(warnings)]
 #![no_main]
@@ -9,9 +15,15 @@ use panic_semihosting as _;
 mod app {
     use cortex_m_semihosting::debug;
 
+    #[shared]
+    struct Shared {}
+
+    #[local]
+    struct Local {}
+
     #[init]
-    fn init(_: init::Context) -> (init::LateResources, init::Monotonics) {
-        (init::LateResources {}, init::Monotonics())
+    fn init(_: init::Context) -> (Shared, Local, init::Monotonics) {
+        (Shared {}, Local {}, init::Monotonics())
     }
 
     #[idle]
@@ -0,0 +1,219 @@
+//! Priority-ceiling and preemption analysis for an `#[app]`, computed from
+//! plain data rather than run inside the (not-yet-existing in this crate)
+//! `#[app]` macro itself.
+//!
+//! A real macro would derive [`TaskDecl`] for every task from its
+//! `priority`, `shared = [..]`, and `lock_free = [..]` arguments and hand
+//! the list to [`ceilings`]/[`preemption`]/[`analyze`], getting back the
+//! [`ResourceCeiling`]/[`TaskPreemption`]/[`Analysis`] values defined here.
+//! With the `analyze` Cargo feature these types derive `serde::Serialize`,
+//! so an [`Analysis`] can be serialized (e.g. to a JSON file); nothing in
+//! this crate writes such a file itself.
+//!
+//! A `#[shared]` field's ceiling is the highest `priority` among the
+//! tasks that name it; a resource declared `lock_free` must be named by
+//! tasks at exactly one priority, and [`ceilings`] reports a
+//! [`LockFreeViolation`] rather than a ceiling if that's ever violated.
+
+#[cfg(feature = "analyze")]
+use serde::Serialize;
+
+use heapless::Vec;
+
+/// One task's priority and the resources it accesses, the way the
+/// `#[app]` macro would derive it from a task's `priority`, `shared =
+/// [..]`, and `lock_free = [..]` arguments.
+#[derive(Debug, Clone, Copy)]
+pub struct TaskDecl<'a> {
+    /// Task (function) name.
+    pub task: &'a str,
+    /// The task's own `priority`.
+    pub priority: u8,
+    /// `#[shared]` fields this task locks.
+    pub shared: &'a [&'a str],
+    /// `#[shared]` fields this task accesses `lock_free`, asserting that
+    /// no other priority ever touches them.
+    pub lock_free: &'a [&'a str],
+}
+
+/// A resource declared `lock_free` was named by tasks running at more
+/// than one priority, contradicting the assertion that it needs no lock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockFreeViolation<'a> {
+    /// The offending resource's name.
+    pub resource: &'a str,
+    /// Two of the (possibly more) distinct priorities that access it.
+    pub priorities: (u8, u8),
+}
+
+/// The priority ceiling computed for one `#[shared]` resource.
+#[cfg_attr(feature = "analyze", derive(Serialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceCeiling<'a> {
+    /// Name of the `#[shared]` struct field.
+    pub resource: &'a str,
+    /// Highest `priority` of any task that accesses this resource.
+    pub ceiling: u8,
+}
+
+/// Which tasks may preempt a given task, per the declared priorities.
+#[cfg_attr(feature = "analyze", derive(Serialize))]
+#[derive(Debug, Clone)]
+pub struct TaskPreemption<'a, const N: usize> {
+    /// Task (function) name.
+    pub task: &'a str,
+    /// The task's own `priority`.
+    pub priority: u8,
+    /// Names of tasks with a strictly higher priority, and therefore able
+    /// to preempt `task`.
+    pub preempted_by: Vec<&'a str, N>,
+}
+
+/// The full analysis of one `#[app]`: a ceiling per `#[shared]`/`lock_free`
+/// resource and a preemption set per task.
+///
+/// `R` bounds the number of distinct resource names and `N` the number of
+/// tasks; both analyses are run over the same `tasks` list, so `N` is
+/// always enough capacity for any one task's `preempted_by`.
+#[cfg_attr(feature = "analyze", derive(Serialize))]
+#[derive(Debug, Clone)]
+pub struct Analysis<'a, const R: usize, const N: usize> {
+    /// One entry per `#[shared]`/`lock_free` field.
+    pub ceilings: Vec<ResourceCeiling<'a>, R>,
+    /// One entry per task (hardware- or software-bound).
+    pub preemption: Vec<TaskPreemption<'a, N>, N>,
+}
+
+fn record_ceiling<'a, const R: usize>(out: &mut Vec<ResourceCeiling<'a>, R>, resource: &'a str, priority: u8) {
+    if let Some(existing) = out.iter_mut().find(|c| c.resource == resource) {
+        if priority > existing.ceiling {
+            existing.ceiling = priority;
+        }
+    } else {
+        // `R`'s doc above makes this the caller's contract: a real
+        // `#[app]` macro sizes it to the app's actual resource count.
+        let _ = out.push(ResourceCeiling { resource, ceiling: priority });
+    }
+}
+
+/// Computes every named resource's priority ceiling, and rejects any
+/// `lock_free` resource found at more than one priority.
+///
+/// `R` bounds how many distinct resource names (`shared` and `lock_free`
+/// combined) the analysis can hold.
+pub fn ceilings<'a, const R: usize>(tasks: &[TaskDecl<'a>]) -> Result<Vec<ResourceCeiling<'a>, R>, LockFreeViolation<'a>> {
+    for t in tasks {
+        for &resource in t.lock_free {
+            for other in tasks {
+                let names_it = other.lock_free.contains(&resource) || other.shared.contains(&resource);
+                if names_it && other.priority != t.priority {
+                    return Err(LockFreeViolation {
+                        resource,
+                        priorities: (t.priority, other.priority),
+                    });
+                }
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    for t in tasks {
+        for &resource in t.shared.iter().chain(t.lock_free) {
+            record_ceiling(&mut out, resource, t.priority);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Builds, for every task, the set of other tasks with strictly higher
+/// priority and therefore able to preempt it.
+///
+/// `N` bounds both the number of tasks and the capacity of each task's
+/// `preempted_by`.
+pub fn preemption<'a, const N: usize>(tasks: &[TaskDecl<'a>]) -> Vec<TaskPreemption<'a, N>, N> {
+    let mut out = Vec::new();
+
+    for t in tasks {
+        let mut preempted_by = Vec::new();
+        for other in tasks {
+            if other.priority > t.priority {
+                // `N`'s doc above makes this the caller's contract: at
+                // most `tasks.len() - 1 < N` entries can ever match.
+                let _ = preempted_by.push(other.task);
+            }
+        }
+
+        let _ = out.push(TaskPreemption {
+            task: t.task,
+            priority: t.priority,
+            preempted_by,
+        });
+    }
+
+    out
+}
+
+/// Runs both [`ceilings`] and [`preemption`] over the same task list.
+pub fn analyze<'a, const R: usize, const N: usize>(tasks: &[TaskDecl<'a>]) -> Result<Analysis<'a, R, N>, LockFreeViolation<'a>> {
+    Ok(Analysis {
+        ceilings: ceilings(tasks)?,
+        preemption: preemption(tasks),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ceiling_is_the_highest_priority_among_accessing_tasks() {
+        let tasks = [
+            TaskDecl { task: "uart0", priority: 1, shared: &["shared"], lock_free: &[] },
+            TaskDecl { task: "uart1", priority: 3, shared: &["shared"], lock_free: &[] },
+        ];
+
+        let ceilings: Vec<ResourceCeiling, 4> = ceilings(&tasks).unwrap();
+        assert_eq!(&ceilings[..], &[ResourceCeiling { resource: "shared", ceiling: 3 }]);
+    }
+
+    #[test]
+    fn lock_free_resource_at_one_priority_gets_its_ceiling() {
+        let tasks = [TaskDecl { task: "uart0", priority: 2, shared: &[], lock_free: &["e1"] }];
+
+        let ceilings: Vec<ResourceCeiling, 4> = ceilings(&tasks).unwrap();
+        assert_eq!(&ceilings[..], &[ResourceCeiling { resource: "e1", ceiling: 2 }]);
+    }
+
+    #[test]
+    fn lock_free_resource_at_two_priorities_is_rejected() {
+        let tasks = [
+            TaskDecl { task: "uart0", priority: 1, shared: &[], lock_free: &["e1"] },
+            TaskDecl { task: "uart1", priority: 2, shared: &[], lock_free: &["e1"] },
+        ];
+
+        let err: Result<Vec<ResourceCeiling, 4>, _> = ceilings(&tasks);
+        assert_eq!(
+            err,
+            Err(LockFreeViolation {
+                resource: "e1",
+                priorities: (1, 2),
+            })
+        );
+    }
+
+    #[test]
+    fn preemption_set_is_every_strictly_higher_priority_task() {
+        let tasks = [
+            TaskDecl { task: "idle", priority: 0, shared: &[], lock_free: &[] },
+            TaskDecl { task: "uart0", priority: 1, shared: &[], lock_free: &[] },
+            TaskDecl { task: "uart1", priority: 2, shared: &[], lock_free: &[] },
+        ];
+
+        let preemption: Vec<TaskPreemption<4>, 4> = preemption(&tasks);
+
+        assert_eq!(&preemption[0].preempted_by[..], &["uart0", "uart1"]);
+        assert_eq!(&preemption[1].preempted_by[..], &["uart1"]);
+        assert!(preemption[2].preempted_by.is_empty());
+    }
+}
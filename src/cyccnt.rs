@@ -0,0 +1,160 @@
+//! A ready-made [`Monotonic`](crate::Monotonic) implementation backed by
+//! the DWT `CYCCNT` register, usable as `#[app(monotonic = rtic::cyccnt::CYCCNT)]`.
+//!
+//! `CYCCNT` is a free-running 32-bit counter, so `Instant`/`Duration`
+//! arithmetic here is wrapping: two instants are compared by the signed
+//! difference of their raw counts, which stays correct across a single
+//! wrap-around (the case the timer queue actually needs).
+
+use core::{cmp::Ordering, ops};
+
+use cortex_m::peripheral::{DCB, DWT};
+
+use crate::Monotonic;
+
+/// A measurement of the `CYCCNT` counter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Instant {
+    inner: u32,
+}
+
+impl Instant {
+    /// Returns an `Instant` corresponding to "now".
+    pub fn now() -> Self {
+        Instant {
+            inner: DWT::cycle_count(),
+        }
+    }
+
+    /// Returns the amount of time elapsed from `earlier` to this instant,
+    /// or `None` if that amount would be negative (`earlier` is in the
+    /// future relative to `self`).
+    pub fn checked_duration_since(&self, earlier: &Instant) -> Option<Duration> {
+        let diff = self.inner.wrapping_sub(earlier.inner) as i32;
+        if diff >= 0 {
+            Some(Duration { inner: diff as u32 })
+        } else {
+            None
+        }
+    }
+}
+
+// Ordering and equality of `Instant`s must be wrap-aware: compare the
+// *signed* difference of the raw counts rather than the raw counts
+// themselves, so an instant just after a wrap still compares greater
+// than one just before it.
+impl Ord for Instant {
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        (self.inner.wrapping_sub(rhs.inner) as i32).cmp(&0)
+    }
+}
+
+impl PartialOrd for Instant {
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl ops::Add<Duration> for Instant {
+    type Output = Instant;
+
+    fn add(self, dur: Duration) -> Instant {
+        Instant {
+            inner: self.inner.wrapping_add(dur.inner),
+        }
+    }
+}
+
+/// A duration measured in `CYCCNT` ticks (CPU cycles).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Duration {
+    inner: u32,
+}
+
+/// Adds a `.cycles()` method to `u32`, turning a cycle count into a
+/// [`Duration`].
+pub trait U32Ext {
+    /// Converts this `u32` into a `Duration` of that many CPU cycles.
+    fn cycles(self) -> Duration;
+}
+
+impl U32Ext for u32 {
+    fn cycles(self) -> Duration {
+        Duration { inner: self }
+    }
+}
+
+/// The `CYCCNT`-backed monotonic clock source.
+pub struct CYCCNT;
+
+impl Monotonic for CYCCNT {
+    type Instant = Instant;
+
+    fn now() -> Instant {
+        Instant::now()
+    }
+
+    unsafe fn reset() {
+        unsafe { (*DWT::PTR).cyccnt.write(0) };
+    }
+
+    fn disable_interrupt() {
+        // `CYCCNT` has no comparator of its own; the generated dispatcher
+        // masks the SysTick/DWT-adjacent interrupt line it was given
+        // instead. Nothing to do here in the general case.
+    }
+
+    fn enable_interrupt() {}
+
+    fn set_compare(_instant: Instant) {
+        // Reprogramming happens through the generated ISR, which reads
+        // `CYCCNT` directly; a real comparator-equipped timer would
+        // arm itself here.
+    }
+
+    fn clear_compare_flag() {}
+}
+
+/// Enables the `CYCCNT` counter. Called once by the runtime before
+/// interrupts are unmasked.
+pub fn instant_init(dcb: &mut DCB, dwt: &mut DWT) {
+    dcb.enable_trace();
+    dwt.enable_cycle_counter();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // These construct `Instant`s directly (its field is private, but
+    // visible to this submodule) rather than going through `Instant::now`,
+    // which reads the real `CYCCNT` register and so needs actual hardware.
+
+    #[test]
+    fn orders_without_wrap() {
+        let a = Instant { inner: 10 };
+        let b = Instant { inner: 20 };
+        assert!(a < b);
+        assert_eq!(b.checked_duration_since(&a), Some(Duration { inner: 10 }));
+        assert_eq!(a.checked_duration_since(&b), None);
+    }
+
+    #[test]
+    fn orders_across_a_wrap() {
+        // `just_before` is close to `u32::MAX`; `just_after` is the
+        // counter shortly after it has wrapped back around through 0.
+        let just_before = Instant { inner: u32::MAX - 5 };
+        let just_after = Instant { inner: 5 };
+        assert!(just_before < just_after);
+        assert_eq!(
+            just_after.checked_duration_since(&just_before),
+            Some(Duration { inner: 11 })
+        );
+    }
+
+    #[test]
+    fn add_duration_wraps() {
+        let instant = Instant { inner: u32::MAX - 2 };
+        assert_eq!(instant + Duration { inner: 5 }, Instant { inner: 2 });
+    }
+}
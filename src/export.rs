@@ -0,0 +1,9 @@
+//! Items used only by code generated by the `#[app]` macro.
+//!
+//! Nothing here is part of the stable API: the macro refers to these
+//! paths (`rtic::export::...`) so user code never needs to import them
+//! directly.
+
+pub use heapless::spsc::Queue;
+
+pub use crate::{local, ready_queue, tq};
@@ -0,0 +1,35 @@
+//! Real-Time Interrupt-driven Concurrency (RTIC) framework runtime support.
+//!
+//! This crate provides the pieces of the framework that are plain Rust APIs
+//! rather than output of the `#[app]` procedural macro: the [`Mutex`] trait
+//! implemented by every resource proxy the macro generates, and small
+//! helpers like [`Exclusive`] that let user code be generic over "is this
+//! resource locked or not".
+
+#![no_std]
+#![deny(missing_docs)]
+
+pub mod analyze;
+pub mod cyccnt;
+pub mod local;
+mod monotonic;
+mod mutex;
+pub mod ready_queue;
+pub mod tq;
+
+#[doc(hidden)]
+pub mod export;
+
+pub use crate::monotonic::Monotonic;
+pub use crate::mutex::{Exclusive, Mutex};
+
+/// Sets the given `interrupt` as pending.
+///
+/// This is a convenience function around
+/// [`NVIC::pend`](../cortex_m/peripheral/struct.NVIC.html#method.pend)
+pub fn pend<I>(interrupt: I)
+where
+    I: cortex_m::interrupt::InterruptNumber,
+{
+    cortex_m::peripheral::NVIC::pend(interrupt);
+}
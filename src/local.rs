@@ -0,0 +1,84 @@
+//! Backing storage for a task's inline `local = [name: Type = expr]`
+//! resources, and the `#[local]` struct fields they generalize (see the
+//! `#[app]` macro's per-task `local` argument).
+//!
+//! For each local the macro allocates one of these as a `static mut`,
+//! writes `expr` into it once from generated `init` code, and exposes
+//! `cx.local.name: &'static mut Type` as a borrow out of it via
+//! [`LocalResource::as_mut`].
+//!
+//! [`LocalResource`] itself enforces none of that: `as_mut` is `unsafe`
+//! precisely because nothing here stops two call sites from aliasing the
+//! same `static`. Single ownership is a property the `#[app]` macro (not
+//! this crate, and not the type system) must establish *before* it emits
+//! any code: the macro walks every task's `local = [..]` list, and it
+//! must reject the app at macro-expansion time -- with a `compile_error!`
+//! pointing at the duplicate, the same way `syn`-based attribute macros
+//! already report other shape errors -- if a field name appears in more
+//! than one task's list. Only once that check has passed is it sound for
+//! the macro to go on and emit exactly one `as_mut` call site per field.
+//! This crate doesn't contain that macro, so the check isn't implemented
+//! here; `LocalResource` is just the storage the macro's generated code
+//! would call into once the check has run.
+//!
+//! `#[shared]` fields go through [`crate::Mutex`] instead precisely
+//! because they're the opposite case: nameable from more than one task,
+//! so they need a runtime lock rather than a single generated accessor.
+//! [`LocalResource`] intentionally has no `Mutex` impl -- there is
+//! nothing to lock when at most one call site is ever supposed to exist.
+
+use core::mem::MaybeUninit;
+
+/// Lazily-initialized, single-owner storage for one inline local
+/// resource.
+pub struct LocalResource<T> {
+    inner: MaybeUninit<T>,
+}
+
+impl<T> LocalResource<T> {
+    /// Creates uninitialized storage, to be [`write`](Self::write)-initialized
+    /// once from `init` before the owning task can run.
+    pub const fn uninit() -> Self {
+        LocalResource {
+            inner: MaybeUninit::uninit(),
+        }
+    }
+
+    /// Initializes the storage with `expr`'s value.
+    ///
+    /// Called exactly once, by generated `init` code, before interrupts
+    /// (and so the owning task) are enabled.
+    pub fn write(&mut self, value: T) {
+        self.inner = MaybeUninit::new(value);
+    }
+
+    /// Borrows the initialized value as `cx.local.name`.
+    ///
+    /// # Safety
+    ///
+    /// Must not be called before [`write`](Self::write). Must also be
+    /// called from at most one call site for a given `LocalResource` --
+    /// nothing here checks that; it's the caller's job (for the `#[app]`
+    /// macro, its compile-time duplicate-`local`-claim check) to
+    /// guarantee it before generating a second call site ever exists.
+    pub unsafe fn as_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.inner.as_mut_ptr() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_then_read_back() {
+        let mut local = LocalResource::uninit();
+        local.write(1u32);
+
+        unsafe {
+            assert_eq!(*local.as_mut(), 1);
+            *local.as_mut() += 1;
+            assert_eq!(*local.as_mut(), 2);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+/// A monotonic clock / counter used to drive the framework's timer queue.
+///
+/// Implement this for a hardware timer (e.g. `SysTick` or the DWT
+/// `CYCCNT`) to make it usable as the `monotonic` clock source of an
+/// `#[app]`: `#[app(device = ..., monotonic = path::to::Impl)]`. The
+/// macro then generates `spawn_after`/`spawn_at` for software tasks and
+/// wires this type's comparator to the timer queue.
+pub trait Monotonic {
+    /// A measurement of this clock, as a point in time. Instants that have
+    /// wrapped around the underlying counter must still compare correctly
+    /// against un-wrapped ones (see the `Ord` impl of `Instant` in
+    /// `cyccnt`).
+    type Instant: Ord + Copy;
+
+    /// Returns the current time.
+    ///
+    /// # Correctness
+    ///
+    /// This function is *allowed* to return nonsensical values if called
+    /// before `reset` is invoked by the runtime.
+    fn now() -> Self::Instant;
+
+    /// Resets the counter to *zero*.
+    ///
+    /// # Safety
+    ///
+    /// This function must only be called once, by the framework, before
+    /// interrupts are enabled.
+    unsafe fn reset();
+
+    /// Disables the hardware comparator / timer interrupt.
+    ///
+    /// Called by the timer-queue dispatcher when the queue becomes
+    /// empty, so the MCU isn't woken up for no reason.
+    fn disable_interrupt();
+
+    /// Enables the hardware comparator / timer interrupt.
+    fn enable_interrupt();
+
+    /// Arms the hardware comparator to fire at, or as soon as possible
+    /// after, `instant`.
+    fn set_compare(instant: Self::Instant);
+
+    /// Clears the comparator/timer interrupt flag.
+    fn clear_compare_flag();
+}
@@ -0,0 +1,50 @@
+/// A type that can be used as a mutually exclusive resource.
+///
+/// Every resource proxy that the `#[app]` macro generates for a `shared`
+/// resource implements this trait: `lock` executes the closure with a
+/// mutable reference to the resource while the appropriate priority
+/// ceiling is upheld (e.g. by raising the current priority, or by
+/// disabling interrupts for the SRP implementation).
+///
+/// Writing code against `Mutex` rather than a concrete proxy type lets a
+/// function be shared between contexts that access the same logical
+/// resource under different locking strategies -- for instance a task
+/// that has real exclusive (`lock_free`/`local`) access and one that must
+/// lock to get at a `shared` resource. See [`Exclusive`] for the former
+/// case.
+pub trait Mutex {
+    /// Data protected by the mutex.
+    type T;
+
+    /// Creates a critical section and grants temporary access to the
+    /// protected data.
+    fn lock<R>(&mut self, f: impl FnOnce(&mut Self::T) -> R) -> R;
+}
+
+impl<M> Mutex for &mut M
+where
+    M: Mutex,
+{
+    type T = M::T;
+
+    fn lock<R>(&mut self, f: impl FnOnce(&mut Self::T) -> R) -> R {
+        (**self).lock(f)
+    }
+}
+
+/// Newtype that wraps a `&mut`-borrowed resource and implements
+/// [`Mutex`] for it by calling `f` directly, with no critical section.
+///
+/// Use this to adapt a resource that is already exclusively accessible --
+/// a `local` resource, or a `lock_free` resource from the one task that is
+/// allowed to touch it -- so it can be passed to a function that is
+/// generic over `Mutex`.
+pub struct Exclusive<'a, T>(pub &'a mut T);
+
+impl<'a, T> Mutex for Exclusive<'a, T> {
+    type T = T;
+
+    fn lock<R>(&mut self, f: impl FnOnce(&mut Self::T) -> R) -> R {
+        f(self.0)
+    }
+}
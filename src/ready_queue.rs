@@ -0,0 +1,128 @@
+//! The per-task ready queue and payload slots behind `spawn`-able software
+//! tasks (see the `#[app]` macro's `capacity` task argument).
+//!
+//! For each software task the macro declares one of these, sized to the
+//! task's `capacity`, as a framework-owned resource: `spawn` writes the
+//! payload into a free slot and pushes the slot index onto the SPSC ready
+//! queue, then pends the dispatcher interrupt reserved for the task's
+//! priority; the dispatcher ISR drains the queue with [`ReadyQueue::dequeue`]
+//! and runs the task once per payload it gets back.
+
+use core::mem::MaybeUninit;
+
+use heapless::spsc::Queue;
+
+/// Fixed-capacity payload storage plus an SPSC ready queue of slot
+/// indices, for one software task.
+///
+/// `N` is the `heapless` queue capacity, which holds `N - 1` usable
+/// slots; a task declared `#[task(capacity = k)]` gets a
+/// `ReadyQueue<Payload, { k + 1 }>`.
+pub struct ReadyQueue<T, const N: usize> {
+    slots: [MaybeUninit<T>; N],
+    // The next slot that has never been handed out; slots below this are
+    // either live (awaiting dispatch) or sitting in `free` to be reused.
+    next: u8,
+    free: Queue<u8, N>,
+    ready: Queue<u8, N>,
+}
+
+impl<T, const N: usize> Default for ReadyQueue<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, const N: usize> ReadyQueue<T, N> {
+    /// Creates an empty ready queue.
+    pub const fn new() -> Self {
+        ReadyQueue {
+            // Safety: an array of `MaybeUninit` needs no initialization.
+            slots: unsafe { MaybeUninit::uninit().assume_init() },
+            next: 0,
+            free: Queue::new(),
+            ready: Queue::new(),
+        }
+    }
+
+    fn take_slot(&mut self) -> Option<u8> {
+        if let Some(slot) = self.free.dequeue() {
+            Some(slot)
+        } else if (self.next as usize) < N - 1 {
+            let slot = self.next;
+            self.next += 1;
+            Some(slot)
+        } else {
+            None
+        }
+    }
+
+    /// Stores `payload` in a free slot and marks it ready to dispatch.
+    ///
+    /// Returns `payload` back on failure -- the queue is already holding
+    /// `capacity` not-yet-dispatched payloads -- mirroring `spawn`'s
+    /// `Result<(), Payload>` return type.
+    ///
+    /// # Safety
+    ///
+    /// Must be called with the dispatcher interrupt masked -- the queue
+    /// is a framework-owned resource shared with the dispatcher ISR.
+    pub unsafe fn spawn(&mut self, payload: T) -> Result<(), T> {
+        match self.take_slot() {
+            Some(slot) => {
+                self.slots[slot as usize] = MaybeUninit::new(payload);
+                // `ready` has the same capacity as `free`/`next`'s range,
+                // so a slot we just took can always be enqueued here.
+                let _ = self.ready.enqueue(slot);
+                Ok(())
+            }
+            None => Err(payload),
+        }
+    }
+
+    /// Pops the next ready payload, for the dispatcher to run, if any.
+    pub fn dequeue(&mut self) -> Option<T> {
+        let slot = self.ready.dequeue()?;
+        // Safety: `slot` was written by `spawn` and not yet read back.
+        let payload = unsafe { self.slots[slot as usize].as_ptr().read() };
+        let _ = self.free.enqueue(slot);
+        Some(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spawn_fails_once_capacity_is_reached() {
+        // `N = 3` gives usable capacity 2 (`heapless` reserves one slot).
+        let mut rq: ReadyQueue<u32, 3> = ReadyQueue::new();
+        unsafe {
+            assert!(rq.spawn(1).is_ok());
+            assert!(rq.spawn(2).is_ok());
+            assert_eq!(rq.spawn(3), Err(3));
+        }
+    }
+
+    #[test]
+    fn dequeue_is_fifo_and_reclaims_slots() {
+        let mut rq: ReadyQueue<u32, 3> = ReadyQueue::new();
+        unsafe {
+            rq.spawn(1).unwrap();
+            rq.spawn(2).unwrap();
+        }
+
+        assert_eq!(rq.dequeue(), Some(1));
+        assert_eq!(rq.dequeue(), Some(2));
+        assert_eq!(rq.dequeue(), None);
+
+        // The slots freed by the dequeues above are reusable.
+        unsafe {
+            rq.spawn(3).unwrap();
+            rq.spawn(4).unwrap();
+        }
+        assert_eq!(rq.dequeue(), Some(3));
+        assert_eq!(rq.dequeue(), Some(4));
+    }
+}
@@ -0,0 +1,214 @@
+//! A priority queue of not-yet-ready software tasks, keyed by the
+//! [`Monotonic`] instant at which they become ready to run.
+//!
+//! The `#[app]` macro generates one `TimerQueue` per application (stored
+//! in a framework-owned, interrupt-shared resource) and pushes onto it
+//! whenever a task is `spawn_after`/`spawn_at`-scheduled. The comparator
+//! ISR drains the queue's *ready* entries and reprograms the hardware for
+//! the next deadline.
+
+use heapless::binary_heap::{BinaryHeap, Min};
+
+use crate::Monotonic;
+
+/// A task that is scheduled to become ready at `instant`, still sitting in
+/// the timer queue.
+pub struct NotReady<Mono, Task>
+where
+    Mono: Monotonic,
+{
+    /// The instant at which this entry should be dequeued.
+    pub instant: Mono::Instant,
+    /// Slot index into the per-task message buffer.
+    pub index: u8,
+    /// Which task to dispatch.
+    pub task: Task,
+}
+
+// `NotReady` is ordered by `instant` alone, earliest first, so that
+// wrapping it in a *min*-heap pops the soonest deadline.
+impl<Mono, Task> Eq for NotReady<Mono, Task> where Mono: Monotonic {}
+
+impl<Mono, Task> PartialEq for NotReady<Mono, Task>
+where
+    Mono: Monotonic,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.instant == other.instant
+    }
+}
+
+impl<Mono, Task> Ord for NotReady<Mono, Task>
+where
+    Mono: Monotonic,
+{
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.instant.cmp(&other.instant)
+    }
+}
+
+impl<Mono, Task> PartialOrd for NotReady<Mono, Task>
+where
+    Mono: Monotonic,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Result of [`TimerQueue::enqueue`]: whether the freshly-inserted entry
+/// became the new head of the queue, i.e. the comparator must be
+/// reprogrammed to its instant.
+pub struct Armed(pub bool);
+
+/// A binary min-heap of [`NotReady`] entries, ordered by `instant`.
+pub struct TimerQueue<Mono, Task, const N: usize>(pub BinaryHeap<NotReady<Mono, Task>, Min, N>)
+where
+    Mono: Monotonic;
+
+impl<Mono, Task, const N: usize> Default for TimerQueue<Mono, Task, N>
+where
+    Mono: Monotonic,
+    Task: Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Mono, Task, const N: usize> TimerQueue<Mono, Task, N>
+where
+    Mono: Monotonic,
+    Task: Copy,
+{
+    /// Creates an empty timer queue.
+    pub const fn new() -> Self {
+        TimerQueue(BinaryHeap::new())
+    }
+
+    /// Adds `task` to the queue, to become ready at `instant`.
+    ///
+    /// Returns `Armed(true)` if `instant` is now the earliest deadline in
+    /// the queue, meaning the caller (the comparator ISR, or whoever
+    /// spawned the task) must reprogram the hardware comparator. Returns
+    /// `Armed(false)` both when the queue already had an earlier deadline
+    /// armed *and* when the queue was full and `nr` could not be inserted
+    /// at all -- the bounded queue silently drops the request rather than
+    /// panicking, and the caller gets no error for the dropped entry; an
+    /// entry that was never inserted can never be the one the comparator
+    /// should be armed for.
+    ///
+    /// # Safety
+    ///
+    /// Must be called with the comparator interrupt masked -- the queue
+    /// is a framework-owned resource shared with the comparator ISR.
+    pub unsafe fn enqueue(&mut self, nr: NotReady<Mono, Task>) -> Armed {
+        let is_new_head = match self.0.peek() {
+            None => true,
+            Some(head) => nr.instant < head.instant,
+        };
+
+        // Always attempt the push -- `&&` short-circuits, so writing
+        // `is_new_head && self.0.push(nr).is_ok()` would skip the push
+        // (and silently drop `nr`) whenever it wasn't the new head.
+        let pushed = self.0.push(nr).is_ok();
+
+        Armed(is_new_head && pushed)
+    }
+
+    /// Pops and dispatches every entry whose instant is `<= now`. Instants
+    /// are compared with `Mono::Instant`'s own `Ord` impl, so timer
+    /// wrap-around is handled exactly as far as that impl handles it (for
+    /// `cyccnt::Instant`: the signed difference of the raw counts).
+    ///
+    /// Returns the instant to next reprogram the comparator for, or
+    /// `None` if the queue is now empty (the caller should disable the
+    /// comparator interrupt in that case).
+    pub fn dequeue(&mut self, now: Mono::Instant, mut dispatch: impl FnMut(Task, u8)) -> Option<Mono::Instant> {
+        while let Some(head) = self.0.peek() {
+            if head.instant <= now {
+                // We just confirmed the heap is non-empty.
+                let nr = self.0.pop().unwrap();
+                dispatch(nr.task, nr.index);
+            } else {
+                return Some(head.instant);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct TestClock;
+
+    impl Monotonic for TestClock {
+        type Instant = u32;
+
+        fn now() -> u32 {
+            0
+        }
+
+        unsafe fn reset() {}
+        fn disable_interrupt() {}
+        fn enable_interrupt() {}
+        fn set_compare(_instant: u32) {}
+        fn clear_compare_flag() {}
+    }
+
+    fn nr(instant: u32, task: u8) -> NotReady<TestClock, u8> {
+        NotReady {
+            instant,
+            index: 0,
+            task,
+        }
+    }
+
+    #[test]
+    fn arms_only_on_a_new_earliest_deadline() {
+        let mut tq: TimerQueue<TestClock, u8, 4> = TimerQueue::new();
+        unsafe {
+            assert!(tq.enqueue(nr(10, 1)).0, "first entry is always the head");
+            assert!(!tq.enqueue(nr(20, 2)).0, "20 is later than the current head (10)");
+            assert!(tq.enqueue(nr(5, 3)).0, "5 is earlier than the current head (10)");
+        }
+    }
+
+    #[test]
+    fn full_queue_is_not_armed_even_if_earlier() {
+        // `heapless::BinaryHeap<_, _, N>` has room for exactly `N` elements.
+        let mut tq: TimerQueue<TestClock, u8, 1> = TimerQueue::new();
+        unsafe {
+            assert!(tq.enqueue(nr(10, 1)).0);
+            assert!(
+                !tq.enqueue(nr(5, 2)).0,
+                "queue is full: the earlier entry was dropped, so nothing new was armed"
+            );
+        }
+    }
+
+    #[test]
+    fn dequeue_drains_ready_entries_and_reports_next_deadline() {
+        let mut tq: TimerQueue<TestClock, u8, 4> = TimerQueue::new();
+        unsafe {
+            tq.enqueue(nr(10, 1));
+            tq.enqueue(nr(20, 2));
+            tq.enqueue(nr(30, 3));
+        }
+
+        let mut dispatched = heapless::Vec::<u8, 4>::new();
+        let next = tq.dequeue(20, |task, _index| dispatched.push(task).unwrap());
+
+        assert_eq!(&dispatched[..], &[1, 2]);
+        assert_eq!(next, Some(30));
+    }
+
+    #[test]
+    fn dequeue_on_empty_queue_returns_none() {
+        let mut tq: TimerQueue<TestClock, u8, 4> = TimerQueue::new();
+        assert_eq!(tq.dequeue(0, |_, _| {}), None);
+    }
+}